@@ -37,6 +37,37 @@
 //! However, some of those asserts are in a sense debug asserts, and I would rather the program continue than crash when it is deployed.
 //! This library lets you have asserts while in release mode, without negatively impacting performance for end users.
 //!
+//! Speaking of not crashing: `RUST_ENV_ASSERT=warn` runs the checks but reports failures instead of panicking, and
+//! `RUST_ENV_ASSERT=abort` reports the failure and then calls `std::process::abort()`. See "Not crashing on failure" below.
+//!
+//! ## More than just `assert!`
+//!
+//! `env_assert!` is a conditional `assert!`, but sometimes what you actually have on hand is two values to compare, or a `Result`/`Option`
+//! you want to sanity check. [`env_assert_eq!`] and [`env_assert_ne!`] are the conditional counterparts of `assert_eq!`/`assert_ne!`, and
+//! [`env_assert_err!`], [`env_assert_ok!`], [`env_assert_some!`], and [`env_assert_none!`] check the shape of a `Result`/`Option`
+//! and panic with the unexpected value if it doesn't match. All of them are gated behind `RUST_ENV_ASSERT=true` the same way
+//! `env_assert!` is, and all of them support the same optional custom-message/format form.
+//!
+//! The `RUST_ENV_ASSERT` lookup itself is cached after the first check, so the macros stay cheap even when they're compiled
+//! into a hot loop. If you change `RUST_ENV_ASSERT` at runtime with `std::env::set_var`, call [`reset_cache`] so the next
+//! check picks up the new value.
+//!
+//! ## Dialing how aggressive checks are
+//!
+//! `RUST_ENV_ASSERT` doesn't have to be just `true`/unset. [`env_assert_level!`] lets each check declare a numeric
+//! level, and only fires if `RUST_ENV_ASSERT` parses to an integer `>=` that level (`env_assert!` and friends are
+//! level `1`, and `RUST_ENV_ASSERT=true` counts as the maximum level). That means you can leave expensive invariant
+//! checks in numeric code and turn on only the cheap ones with `RUST_ENV_ASSERT=1`, or everything with a higher
+//! number, without recompiling.
+//!
+//! ## Not crashing on failure
+//!
+//! `RUST_ENV_ASSERT=true` (or a numeric level) panics on failure like `assert!` always has. But `RUST_ENV_ASSERT=warn`
+//! runs the exact same checks and, on failure, reports the formatted message and `file:line` through a hook -- by
+//! default `eprintln!` -- without panicking, and `RUST_ENV_ASSERT=abort` reports the same way and then calls
+//! `std::process::abort()`. Override the hook with [`set_failure_handler`] to route failures into your own
+//! logging/metrics instead of stderr.
+//!
 //! ## Should I use this?
 //!
 //! Eh, probably not.
@@ -44,79 +75,742 @@
 #[macro_export]
 macro_rules! env_assert {
     ($cond:expr) => {{
-        const KEY: &'static str = "RUST_ENV_ASSERT";
-        match std::env::var(KEY) {
-            Ok(v) => if v == "true" {
-                assert!($cond)
-            }
-            _ => ()
+        if $crate::enabled() && !($cond) {
+            $crate::handle_failure(format!(
+                "[{}:{}] assertion failed: {}",
+                file!(), line!(), stringify!($cond)
+            ));
         }
     }};
     ($cond:expr,) => {{
-        const KEY: &'static str = "RUST_ENV_ASSERT";
-        match std::env::var(KEY) {
-            Ok(v) => if v == "true" {
-                assert!($cond)
+        $crate::env_assert!($cond)
+    }};
+    ($cond:expr, $($arg:tt)+) => {{
+        if $crate::enabled() && !($cond) {
+            $crate::handle_failure(format!("[{}:{}] {}", file!(), line!(), format!($($arg)+)));
+        }
+    }};
+}
+
+/// Like [`env_assert!`], but only fires when `RUST_ENV_ASSERT` parses to an integer `>=` the given `$level`
+/// (`RUST_ENV_ASSERT=true` counts as the maximum level, for backwards compatibility).
+///
+/// This lets you sprinkle cheap and expensive invariant checks alike through a codebase and dial
+/// how aggressive they are per environment, e.g. `RUST_ENV_ASSERT=3` in staging and `RUST_ENV_ASSERT=1`
+/// in a more production-like build, without recompiling.
+#[macro_export]
+macro_rules! env_assert_level {
+    ($level:expr, $cond:expr) => {{
+        if $crate::level() >= $level && !($cond) {
+            $crate::handle_failure(format!(
+                "[{}:{}] assertion failed: {}",
+                file!(), line!(), stringify!($cond)
+            ));
+        }
+    }};
+    ($level:expr, $cond:expr,) => {{
+        $crate::env_assert_level!($level, $cond)
+    }};
+    ($level:expr, $cond:expr, $($arg:tt)+) => {{
+        if $crate::level() >= $level && !($cond) {
+            $crate::handle_failure(format!("[{}:{}] {}", file!(), line!(), format!($($arg)+)));
+        }
+    }};
+}
+
+/// Like [`env_assert!`], but checks that two values are equal (like `assert_eq!`) instead of asserting a single `bool`.
+#[macro_export]
+macro_rules! env_assert_eq {
+    ($left:expr, $right:expr) => {{
+        if $crate::enabled() {
+            match (&$left, &$right) {
+                (left, right) => {
+                    if !(*left == *right) {
+                        $crate::handle_failure(format!(
+                            "[{}:{}] assertion `left == right` failed\n  left: {:?}\n right: {:?}",
+                            file!(), line!(), left, right
+                        ));
+                    }
+                }
             }
-            _ => ()
         }
     }};
-    ($cond:expr, $($arg:tt)+) => {{
-        const KEY: &'static str = "RUST_ENV_ASSERT";
-        match std::env::var(KEY) {
-            Ok(v) => if v == "true" {
-                let s = format!($($arg)+);
-                assert!($cond, s)
+    ($left:expr, $right:expr,) => {{
+        $crate::env_assert_eq!($left, $right)
+    }};
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        if $crate::enabled() {
+            match (&$left, &$right) {
+                (left, right) => {
+                    if !(*left == *right) {
+                        $crate::handle_failure(format!("[{}:{}] {}", file!(), line!(), format!($($arg)+)));
+                    }
+                }
             }
-            _ => ()
         }
+    }};
+}
 
+/// Like [`env_assert!`], but checks that two values are not equal (like `assert_ne!`) instead of asserting a single `bool`.
+#[macro_export]
+macro_rules! env_assert_ne {
+    ($left:expr, $right:expr) => {{
+        if $crate::enabled() {
+            match (&$left, &$right) {
+                (left, right) => {
+                    if *left == *right {
+                        $crate::handle_failure(format!(
+                            "[{}:{}] assertion `left != right` failed\n  left: {:?}\n right: {:?}",
+                            file!(), line!(), left, right
+                        ));
+                    }
+                }
+            }
+        }
+    }};
+    ($left:expr, $right:expr,) => {{
+        $crate::env_assert_ne!($left, $right)
+    }};
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        if $crate::enabled() {
+            match (&$left, &$right) {
+                (left, right) => {
+                    if *left == *right {
+                        $crate::handle_failure(format!("[{}:{}] {}", file!(), line!(), format!($($arg)+)));
+                    }
+                }
+            }
+        }
     }};
 }
 
-// Note, tests should be run with the environmental variable set, aka RUST_ENV_ASSERT=true cargo test
-#[cfg(test)]
-mod tests {
-    const KEY: &'static str = "RUST_ENV_ASSERT";
+/// Like [`env_assert!`], but asserts that a `Result` is `Err(..)`, reporting the unexpected `Ok(..)` otherwise.
+#[macro_export]
+macro_rules! env_assert_err {
+    ($res:expr) => {{
+        if $crate::enabled() {
+            match $res {
+                Err(_) => (),
+                Ok(ref t) => $crate::handle_failure(format!(
+                    "[{}:{}] assertion failed, expected `Err(..)`, got `Ok({:?})`",
+                    file!(), line!(), t
+                )),
+            }
+        }
+    }};
+    ($res:expr,) => {
+        $crate::env_assert_err!($res)
+    };
+    ($res:expr, $($arg:tt)+) => {{
+        if $crate::enabled() {
+            match $res {
+                Err(_) => (),
+                Ok(ref t) => $crate::handle_failure(format!(
+                    "[{}:{}] assertion failed, expected `Err(..)`, got `Ok({:?})`: {}",
+                    file!(), line!(), t, format!($($arg)+)
+                )),
+            }
+        }
+    }};
+}
 
-    fn set_var_to_true() {
-        std::env::set_var(KEY, "true");
+/// Like [`env_assert!`], but asserts that a `Result` is `Ok(..)`, reporting the unexpected `Err(..)` otherwise.
+#[macro_export]
+macro_rules! env_assert_ok {
+    ($res:expr) => {{
+        if $crate::enabled() {
+            match $res {
+                Ok(_) => (),
+                Err(ref e) => $crate::handle_failure(format!(
+                    "[{}:{}] assertion failed, expected `Ok(..)`, got `Err({:?})`",
+                    file!(), line!(), e
+                )),
+            }
+        }
+    }};
+    ($res:expr,) => {
+        $crate::env_assert_ok!($res)
+    };
+    ($res:expr, $($arg:tt)+) => {{
+        if $crate::enabled() {
+            match $res {
+                Ok(_) => (),
+                Err(ref e) => $crate::handle_failure(format!(
+                    "[{}:{}] assertion failed, expected `Ok(..)`, got `Err({:?})`: {}",
+                    file!(), line!(), e, format!($($arg)+)
+                )),
+            }
+        }
+    }};
+}
+
+/// Like [`env_assert!`], but asserts that an `Option` is `Some(..)`, reporting `None` otherwise.
+#[macro_export]
+macro_rules! env_assert_some {
+    ($opt:expr) => {{
+        if $crate::enabled() {
+            match $opt {
+                Some(_) => (),
+                None => $crate::handle_failure(format!(
+                    "[{}:{}] assertion failed, expected `Some(..)`, got `None`",
+                    file!(), line!()
+                )),
+            }
+        }
+    }};
+    ($opt:expr,) => {
+        $crate::env_assert_some!($opt)
+    };
+    ($opt:expr, $($arg:tt)+) => {{
+        if $crate::enabled() {
+            match $opt {
+                Some(_) => (),
+                None => $crate::handle_failure(format!(
+                    "[{}:{}] assertion failed, expected `Some(..)`, got `None`: {}",
+                    file!(), line!(), format!($($arg)+)
+                )),
+            }
+        }
+    }};
+}
+
+/// Like [`env_assert!`], but asserts that an `Option` is `None`, reporting the unexpected `Some(..)` otherwise.
+#[macro_export]
+macro_rules! env_assert_none {
+    ($opt:expr) => {{
+        if $crate::enabled() {
+            match $opt {
+                None => (),
+                Some(ref t) => $crate::handle_failure(format!(
+                    "[{}:{}] assertion failed, expected `None`, got `Some({:?})`",
+                    file!(), line!(), t
+                )),
+            }
+        }
+    }};
+    ($opt:expr,) => {
+        $crate::env_assert_none!($opt)
+    };
+    ($opt:expr, $($arg:tt)+) => {{
+        if $crate::enabled() {
+            match $opt {
+                None => (),
+                Some(ref t) => $crate::handle_failure(format!(
+                    "[{}:{}] assertion failed, expected `None`, got `Some({:?})`: {}",
+                    file!(), line!(), t, format!($($arg)+)
+                )),
+            }
+        }
+    }};
+}
+
+const KEY: &'static str = "RUST_ENV_ASSERT";
+
+/// The level `RUST_ENV_ASSERT=true` (and `RUST_ENV_ASSERT=warn`/`RUST_ENV_ASSERT=abort`) map to,
+/// for backwards compatibility with code that predates [`env_assert_level!`] and just wants
+/// "everything on".
+const MAX_LEVEL: u32 = u32::MAX;
+
+/// How a failing check is surfaced, derived from `RUST_ENV_ASSERT`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum FailureMode {
+    /// `RUST_ENV_ASSERT=true` or a numeric level: panic, like `assert!` always has.
+    Panic,
+    /// `RUST_ENV_ASSERT=warn`: report through the failure handler and keep going.
+    Warn,
+    /// `RUST_ENV_ASSERT=abort`: report through the failure handler, then `std::process::abort()`.
+    Abort,
+}
+
+// Cache for `level()`/`failure_mode()`: `CACHE_INIT` stays false until the first read, at which
+// point `LEVEL` and `FAILURE_MODE` are pinned to whatever `RUST_ENV_ASSERT` parsed to, so later
+// checks are a couple of atomic loads instead of a fresh `std::env::var` lookup (which allocates
+// and touches the process environment every time).
+static CACHE_INIT: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+static LEVEL: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+static FAILURE_MODE: std::sync::atomic::AtomicU8 = std::sync::atomic::AtomicU8::new(0);
+
+fn parse(var: Option<String>) -> (u32, FailureMode) {
+    match var.as_deref() {
+        None => (0, FailureMode::Panic),
+        Some("true") => (MAX_LEVEL, FailureMode::Panic),
+        Some("warn") => (MAX_LEVEL, FailureMode::Warn),
+        Some("abort") => (MAX_LEVEL, FailureMode::Abort),
+        Some(s) => (s.parse().unwrap_or(0), FailureMode::Panic),
+    }
+}
+
+fn ensure_cached() {
+    // `CACHE_INIT`'s Release store happens after the `LEVEL`/`FAILURE_MODE` stores below, and its
+    // Acquire load here happens before reading them back, so a thread that observes `true` is
+    // guaranteed to also observe the `LEVEL`/`FAILURE_MODE` values that went with it, even on a
+    // weak-memory target.
+    if !CACHE_INIT.load(std::sync::atomic::Ordering::Acquire) {
+        let (level, mode) = parse(std::env::var(KEY).ok());
+        LEVEL.store(level, std::sync::atomic::Ordering::Relaxed);
+        FAILURE_MODE.store(mode as u8, std::sync::atomic::Ordering::Relaxed);
+        CACHE_INIT.store(true, std::sync::atomic::Ordering::Release);
+    }
+}
+
+/// Returns the level `RUST_ENV_ASSERT` is currently parsed as: `0` if it's unset or not a
+/// recognized value, the parsed integer if it holds one, or [`MAX_LEVEL`] if it's `"true"`,
+/// `"warn"`, or `"abort"`.
+///
+/// The result is cached after the first call, so this is just a couple of atomic loads on the
+/// common path. If you mutate `RUST_ENV_ASSERT` at runtime (e.g. via `std::env::set_var`), the
+/// change won't be picked up until you call [`reset_cache`].
+#[doc(hidden)]
+pub fn level() -> u32 {
+    ensure_cached();
+    LEVEL.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+fn failure_mode() -> FailureMode {
+    ensure_cached();
+    match FAILURE_MODE.load(std::sync::atomic::Ordering::Relaxed) {
+        1 => FailureMode::Warn,
+        2 => FailureMode::Abort,
+        _ => FailureMode::Panic,
+    }
+}
+
+fn default_failure_handler(msg: &str) {
+    eprintln!("{}", msg);
+}
+
+static FAILURE_HANDLER: std::sync::RwLock<fn(&str)> =
+    std::sync::RwLock::new(default_failure_handler as fn(&str));
+
+/// Overrides the hook called when a `RUST_ENV_ASSERT=warn` (or `=abort`) check fails, instead of
+/// the default `eprintln!`.
+///
+/// Useful for routing failures into your own logging/metrics instead of stderr.
+pub fn set_failure_handler(handler: fn(&str)) {
+    *FAILURE_HANDLER.write().unwrap() = handler;
+}
+
+// Like `ENV_ASSERT_LOCK`, but for `FAILURE_HANDLER`: serializes `with_failure_handler` calls so
+// concurrent tests can't leak a custom handler into one another. Kept separate from
+// `ENV_ASSERT_LOCK` since a test may legitimately hold both at once (e.g. running inside
+// `with_env_assert`/`without_env_assert`), and a single shared lock would deadlock on re-entry.
+static FAILURE_HANDLER_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Restores the previous failure handler when dropped, mirroring [`EnvAssertGuard`].
+struct FailureHandlerGuard {
+    prior: fn(&str),
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+impl Drop for FailureHandlerGuard {
+    fn drop(&mut self) {
+        *FAILURE_HANDLER.write().unwrap() = self.prior;
+    }
+}
+
+/// Runs `f` with the failure handler set to `handler`, restoring whatever handler was set
+/// beforehand once `f` returns, even if `f` panics.
+///
+/// This is meant for tests that want to capture [`handle_failure`]'s output deterministically,
+/// without leaking a custom handler into other tests running in parallel: calls are serialized on
+/// a process-wide lock, same as [`with_env_assert`].
+pub fn with_failure_handler(handler: fn(&str), f: impl FnOnce()) {
+    let lock = FAILURE_HANDLER_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let prior = *FAILURE_HANDLER.read().unwrap();
+    let _guard = FailureHandlerGuard { prior, _lock: lock };
+    *FAILURE_HANDLER.write().unwrap() = handler;
+    f();
+}
+
+/// Reports a failing check according to the current [`FailureMode`]: panics, reports through the
+/// failure handler, or reports and then aborts the process.
+///
+/// Called by the `env_assert*!` macros with an already-formatted message; not meant to be called
+/// directly.
+#[doc(hidden)]
+pub fn handle_failure(msg: String) {
+    match failure_mode() {
+        FailureMode::Panic => panic!("{}", msg),
+        FailureMode::Warn => (FAILURE_HANDLER.read().unwrap())(&msg),
+        FailureMode::Abort => {
+            (FAILURE_HANDLER.read().unwrap())(&msg);
+            std::process::abort();
+        }
+    }
+}
+
+/// Returns whether `RUST_ENV_ASSERT` is set to a value that enables level `1` checks, i.e.
+/// whether `env_assert!` (and friends) should run at all.
+#[doc(hidden)]
+pub fn enabled() -> bool {
+    level() >= 1
+}
+
+/// Forces the next call to [`level`] (and [`enabled`]) to re-read `RUST_ENV_ASSERT` from the
+/// environment.
+///
+/// Call this after mutating `RUST_ENV_ASSERT` at runtime; [`level`] caches the variable's value
+/// after its first read and won't otherwise notice the change.
+pub fn reset_cache() {
+    CACHE_INIT.store(false, std::sync::atomic::Ordering::Relaxed);
+}
+
+// `RUST_ENV_ASSERT` and the `level()`/`failure_mode()` cache it feeds are process-wide state, but
+// Rust's default test harness runs `#[test]` fns concurrently on multiple threads in the same
+// process. `with_env_assert`/`without_env_assert` serialize every call through this lock so two
+// tests can't interleave their set_var/reset_cache/restore sequences.
+static ENV_ASSERT_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Restores whatever `RUST_ENV_ASSERT` was set to (or unset, if it wasn't set at all) when dropped.
+///
+/// Holds `ENV_ASSERT_LOCK` for its whole lifetime: the lock is acquired before the prior value is
+/// captured and only released (via the `_lock` field's own drop, which runs after `Drop::drop`
+/// below restores the env var) once that restoration is complete.
+struct EnvAssertGuard {
+    prior: Option<String>,
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+impl Drop for EnvAssertGuard {
+    fn drop(&mut self) {
+        match &self.prior {
+            Some(v) => std::env::set_var(KEY, v),
+            None => std::env::remove_var(KEY),
+        }
+        reset_cache();
     }
+}
 
-    fn remove_var() {
+/// Runs `f` with `RUST_ENV_ASSERT` set to `"true"` (if `enabled`) or unset (if not), restoring
+/// whatever the variable was set to beforehand once `f` returns, even if `f` panics.
+///
+/// This is meant for tests that want to exercise both the triggered and skipped paths of
+/// `env_assert!` (and friends) deterministically, without leaking `RUST_ENV_ASSERT` into other
+/// tests running in parallel: calls are serialized on a process-wide lock, so two tests running
+/// on different threads can't stomp on each other's env var or cache state.
+pub fn with_env_assert(enabled: bool, f: impl FnOnce()) {
+    let lock = ENV_ASSERT_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let _guard = EnvAssertGuard {
+        prior: std::env::var(KEY).ok(),
+        _lock: lock,
+    };
+    if enabled {
+        std::env::set_var(KEY, "true");
+    } else {
         std::env::remove_var(KEY);
     }
+    reset_cache();
+    f();
+}
+
+/// Shorthand for `with_env_assert(false, f)`.
+pub fn without_env_assert(f: impl FnOnce()) {
+    with_env_assert(false, f)
+}
+
+// Note, the tests set or unset the environmental variable themselves, so a plain `cargo test` is enough
+#[cfg(test)]
+mod tests {
+    use super::{with_env_assert, without_env_assert};
 
     #[test]
     fn just_true() {
-        set_var_to_true();
-        super::env_assert!(true);
+        with_env_assert(true, || {
+            super::env_assert!(true);
+        });
     }
 
     #[test]
     fn true_with_comma() {
-        set_var_to_true();
-        super::env_assert!(true,);
+        with_env_assert(true, || {
+            super::env_assert!(true,);
+        });
     }
 
     #[test]
     fn true_with_fmt() {
-        set_var_to_true();
-        super::env_assert!(true, "didn't crash with {}", 5);
+        with_env_assert(true, || {
+            super::env_assert!(true, "didn't crash with {}", 5);
+        });
     }
 
     #[test]
     #[should_panic(expected = "false assert is panic")]
     fn test_panic_var_true() {
-        set_var_to_true();
-        super::env_assert!(false, "false assert is panic");
+        with_env_assert(true, || {
+            super::env_assert!(false, "false assert is panic");
+        });
     }
 
     #[test]
     fn assert_when_var_is_not_set() {
-        remove_var();
-        assert!(std::env::var(KEY).is_err());
-        super::env_assert!(true, "asserting with true");
-        super::env_assert!(false, "asserting with false");
+        without_env_assert(|| {
+            assert!(std::env::var(super::KEY).is_err());
+            super::env_assert!(true, "asserting with true");
+            super::env_assert!(false, "asserting with false");
+        });
+    }
+
+    #[test]
+    fn eq_passes() {
+        with_env_assert(true, || {
+            super::env_assert_eq!(1, 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion")]
+    fn eq_panics_when_different() {
+        with_env_assert(true, || {
+            super::env_assert_eq!(1, 2);
+        });
+    }
+
+    #[test]
+    fn eq_skipped_when_var_is_not_set() {
+        without_env_assert(|| {
+            super::env_assert_eq!(1, 2);
+        });
+    }
+
+    #[test]
+    fn ne_passes() {
+        with_env_assert(true, || {
+            super::env_assert_ne!(1, 2);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion")]
+    fn ne_panics_when_equal() {
+        with_env_assert(true, || {
+            super::env_assert_ne!(1, 1);
+        });
+    }
+
+    #[test]
+    fn ne_skipped_when_var_is_not_set() {
+        without_env_assert(|| {
+            super::env_assert_ne!(1, 1);
+        });
+    }
+
+    #[test]
+    fn err_passes() {
+        with_env_assert(true, || {
+            let res: Result<i32, i32> = Err(1);
+            super::env_assert_err!(res);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "got `Ok(1)`")]
+    fn err_panics_with_unexpected_ok() {
+        with_env_assert(true, || {
+            let res: Result<i32, i32> = Ok(1);
+            super::env_assert_err!(res);
+        });
+    }
+
+    #[test]
+    fn err_skipped_when_var_is_not_set() {
+        without_env_assert(|| {
+            let res: Result<i32, i32> = Ok(1);
+            super::env_assert_err!(res);
+        });
+    }
+
+    #[test]
+    fn ok_passes() {
+        with_env_assert(true, || {
+            let res: Result<i32, i32> = Ok(1);
+            super::env_assert_ok!(res);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "got `Err(1)`")]
+    fn ok_panics_with_unexpected_err() {
+        with_env_assert(true, || {
+            let res: Result<i32, i32> = Err(1);
+            super::env_assert_ok!(res);
+        });
+    }
+
+    #[test]
+    fn ok_skipped_when_var_is_not_set() {
+        without_env_assert(|| {
+            let res: Result<i32, i32> = Err(1);
+            super::env_assert_ok!(res);
+        });
+    }
+
+    #[test]
+    fn some_passes() {
+        with_env_assert(true, || {
+            let opt: Option<i32> = Some(1);
+            super::env_assert_some!(opt);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "got `None`")]
+    fn some_panics_with_unexpected_none() {
+        with_env_assert(true, || {
+            let opt: Option<i32> = None;
+            super::env_assert_some!(opt);
+        });
+    }
+
+    #[test]
+    fn some_skipped_when_var_is_not_set() {
+        without_env_assert(|| {
+            let opt: Option<i32> = None;
+            super::env_assert_some!(opt);
+        });
+    }
+
+    #[test]
+    fn none_passes() {
+        with_env_assert(true, || {
+            let opt: Option<i32> = None;
+            super::env_assert_none!(opt);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "got `Some(1)`")]
+    fn none_panics_with_unexpected_some() {
+        with_env_assert(true, || {
+            let opt: Option<i32> = Some(1);
+            super::env_assert_none!(opt);
+        });
+    }
+
+    #[test]
+    fn none_skipped_when_var_is_not_set() {
+        without_env_assert(|| {
+            let opt: Option<i32> = Some(1);
+            super::env_assert_none!(opt);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "level 3 fired")]
+    fn level_fires_when_var_meets_level() {
+        without_env_assert(|| {
+            std::env::set_var(super::KEY, "3");
+            super::reset_cache();
+            super::env_assert_level!(3, false, "level 3 fired");
+            std::env::remove_var(super::KEY);
+            super::reset_cache();
+        });
+    }
+
+    #[test]
+    fn level_skipped_when_var_is_below_level() {
+        without_env_assert(|| {
+            std::env::set_var(super::KEY, "2");
+            super::reset_cache();
+            super::env_assert_level!(3, false, "level too low");
+            std::env::remove_var(super::KEY);
+            super::reset_cache();
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "max level fired")]
+    fn level_treats_true_as_max_level() {
+        with_env_assert(true, || {
+            super::env_assert_level!(1_000_000, false, "max level fired");
+        });
+    }
+
+    #[test]
+    fn warn_mode_reports_instead_of_panicking() {
+        static CAPTURED: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+        fn capture(msg: &str) {
+            CAPTURED.lock().unwrap().push(msg.to_string());
+        }
+
+        without_env_assert(|| {
+            std::env::set_var(super::KEY, "warn");
+            super::reset_cache();
+
+            super::with_failure_handler(capture, || {
+                super::env_assert!(false, "should warn, not panic");
+            });
+
+            std::env::remove_var(super::KEY);
+            super::reset_cache();
+        });
+
+        let captured = CAPTURED.lock().unwrap();
+        assert_eq!(captured.len(), 1);
+        assert!(captured[0].contains("should warn, not panic"));
+    }
+
+    #[test]
+    fn warn_mode_treats_level_as_max_level() {
+        static CAPTURED: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+        fn capture(msg: &str) {
+            CAPTURED.lock().unwrap().push(msg.to_string());
+        }
+
+        without_env_assert(|| {
+            std::env::set_var(super::KEY, "warn");
+            super::reset_cache();
+
+            super::with_failure_handler(capture, || {
+                super::env_assert_level!(5, false, "high level should still warn");
+            });
+
+            std::env::remove_var(super::KEY);
+            super::reset_cache();
+        });
+
+        let captured = CAPTURED.lock().unwrap();
+        assert_eq!(captured.len(), 1);
+        assert!(captured[0].contains("high level should still warn"));
+    }
+
+    #[test]
+    fn abort_mode_is_parsed_from_env_var() {
+        without_env_assert(|| {
+            std::env::set_var(super::KEY, "abort");
+            super::reset_cache();
+            assert_eq!(super::failure_mode(), super::FailureMode::Abort);
+            assert_eq!(super::level(), u32::MAX);
+            std::env::remove_var(super::KEY);
+            super::reset_cache();
+        });
+    }
+
+    #[test]
+    fn with_env_assert_restores_prior_value_after_panic() {
+        std::env::set_var(super::KEY, "before");
+        let result = std::panic::catch_unwind(|| {
+            with_env_assert(true, || panic!("boom"));
+        });
+        assert!(result.is_err());
+        assert_eq!(std::env::var(super::KEY).unwrap(), "before");
+        std::env::remove_var(super::KEY);
+    }
+
+    #[test]
+    fn enabled_reflects_stale_env_var_until_reset() {
+        without_env_assert(|| {
+            assert!(!super::enabled());
+            std::env::set_var(super::KEY, "true");
+            assert!(!super::enabled(), "cached value shouldn't change without a reset");
+            super::reset_cache();
+            assert!(super::enabled());
+            std::env::remove_var(super::KEY);
+            super::reset_cache();
+        });
     }
 }